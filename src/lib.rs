@@ -1,9 +1,64 @@
 use std::{
+    cell::RefCell,
     ffi::{c_char, c_int, c_ulong, CStr, c_void},
     mem,
 };
 
+thread_local! {
+    static LAST_ERROR: RefCell<String> = RefCell::new(String::new());
+}
+
+fn set_last_error(message: String) {
+    LAST_ERROR.with(|cell| *cell.borrow_mut() = message);
+}
+
+// Allocates a `libc::malloc`'d, NUL-terminated copy of `s`. Ownership passes
+// to the caller, who must release it with `free_string`.
+// Strip interior NULs rather than truncating the C string at the first one.
+fn alloc_c_string(s: &str) -> *mut c_char {
+    let mut bytes: Vec<u8> = s.bytes().filter(|&b| b != 0).collect();
+    bytes.push(0);
+    unsafe {
+        let ptr = libc::malloc(mem::size_of::<c_char>() * bytes.len()) as *mut c_char;
+        if ptr.is_null() {
+            return ptr;
+        }
+        for i in 0..bytes.len() {
+            *ptr.offset(i.try_into().unwrap()) = bytes[i] as c_char;
+        }
+        ptr
+    }
+}
+
+// Wraps a successfully computed string in an `Ok` `CharResult`, or an
+// allocation-failure error if `alloc_c_string` couldn't get memory from
+// `libc::malloc` - so a null payload is never reported as `StatusCode::Ok`.
+fn char_result_ok(s: &str) -> CharResult {
+    let ptr = alloc_c_string(s);
+    if ptr.is_null() {
+        return CharResult {
+            status_code: StatusCode::Err,
+            result: CharUnion { err: (-14i8).into() },
+        };
+    }
+    CharResult {
+        status_code: StatusCode::Ok,
+        result: CharUnion { ok: ptr },
+    }
+}
+
+// Built once per `init_auth*` call and then handed to `core::Auth` so every
+// other entry point can reuse it via `Auth::runtime` instead of spinning up
+// a fresh scheduler per call.
+fn build_runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+}
+
 fn get_error_code(err: core::AuthError) -> i8 {
+    set_last_error(format!("{:?}", err));
     match err {
         core::AuthError::UserAlreadyExists => -1,
         core::AuthError::UserDoesNotExist => -2,
@@ -13,6 +68,33 @@ fn get_error_code(err: core::AuthError) -> i8 {
         core::AuthError::UnableToAquireTokenListLock => -6,
         core::AuthError::PostgresError(_) => -7,
         core::AuthError::RedisError(_) => -8,
+        core::AuthError::TotpRequired => -10,
+        core::AuthError::IncorrectTotpCode => -11,
+        core::AuthError::UnsupportedOperation => -12,
+        core::AuthError::Unauthorized => -13,
+    }
+}
+
+/// Returns the message for the most recent `core::AuthError` reported on
+/// this thread, or an empty string if none has been recorded yet. Errors
+/// raised by this FFI layer itself (such as `-9` for a non-UTF-8 argument)
+/// are not recorded and leave the previous message in place. The returned pointer is
+/// caller-owned and must be released with `free_string`. Returns
+/// a null pointer if the message couldn't be allocated.
+#[no_mangle]
+pub extern "C" fn last_error_message() -> *mut c_char {
+    LAST_ERROR.with(|cell| alloc_c_string(&cell.borrow()))
+}
+
+/// Frees a string previously returned by this library (e.g. from `login`,
+/// `verify_token`, or `last_error_message`).
+#[no_mangle]
+pub extern "C" fn free_string(s: *mut c_char) {
+    if s.is_null() {
+        return;
+    }
+    unsafe {
+        libc::free(s as *mut c_void);
     }
 }
 
@@ -76,15 +158,93 @@ pub extern "C" fn init_auth(postgres_url: *mut c_char, redis_url: *mut c_char) -
         Ok(s) => s,
         Err(_) => return error(-9),
     };
-    let auth = tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()
-        .unwrap()
+    let runtime = build_runtime();
+    let auth = runtime
         .block_on(async { core::init_auth(postgres_url.to_string(), redis_url.to_string()).await });
-    let auth = match auth {
+    let mut auth = match auth {
+        Ok(a) => a,
+        Err(err) => return error(get_error_code(err)),
+    };
+    auth.set_runtime(runtime);
+    AuthResult {
+        status_code: StatusCode::Ok,
+        result: AuthUnion {
+            ok: Box::into_raw(Box::new(auth)),
+        },
+    }
+}
+
+#[repr(C)]
+pub enum TokenAlgorithm {
+    Hs256,
+    Rs256,
+}
+
+impl From<TokenAlgorithm> for core::TokenAlgorithm {
+    fn from(value: TokenAlgorithm) -> Self {
+        match value {
+            TokenAlgorithm::Hs256 => core::TokenAlgorithm::Hs256,
+            TokenAlgorithm::Rs256 => core::TokenAlgorithm::Rs256,
+        }
+    }
+}
+
+/// Like `init_auth`, but `login` mints a self-contained, signed JWT instead
+/// of an opaque token that `verify_token` would otherwise have to look up
+/// in Redis. `signing_key` is the HS256 shared secret or the RS256 private
+/// key; `verifying_key` is the RS256 public key and is ignored for HS256
+/// (pass a null pointer). Redis is still used, but only to hold revoked
+/// `jti`s so `logout` keeps working.
+#[no_mangle]
+pub extern "C" fn init_auth_jwt(
+    postgres_url: *mut c_char,
+    redis_url: *mut c_char,
+    algorithm: TokenAlgorithm,
+    signing_key: *mut c_char,
+    verifying_key: *mut c_char,
+) -> AuthResult {
+    fn error(code: i8) -> AuthResult {
+        AuthResult {
+            status_code: StatusCode::Err,
+            result: AuthUnion { err: code.into() },
+        }
+    }
+    let postgres_url = match unsafe { CStr::from_ptr(postgres_url) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return error(-9),
+    };
+    let redis_url = match unsafe { CStr::from_ptr(redis_url) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return error(-9),
+    };
+    let signing_key = match unsafe { CStr::from_ptr(signing_key) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return error(-9),
+    };
+    let verifying_key = if verifying_key.is_null() {
+        None
+    } else {
+        match unsafe { CStr::from_ptr(verifying_key) }.to_str() {
+            Ok(s) => Some(s.to_string()),
+            Err(_) => return error(-9),
+        }
+    };
+    let runtime = build_runtime();
+    let auth = runtime.block_on(async {
+        core::init_auth_jwt(
+            postgres_url.to_string(),
+            redis_url.to_string(),
+            algorithm.into(),
+            signing_key.to_string(),
+            verifying_key,
+        )
+        .await
+    });
+    let mut auth = match auth {
         Ok(a) => a,
         Err(err) => return error(get_error_code(err)),
     };
+    auth.set_runtime(runtime);
     AuthResult {
         status_code: StatusCode::Ok,
         result: AuthUnion {
@@ -93,13 +253,77 @@ pub extern "C" fn init_auth(postgres_url: *mut c_char, redis_url: *mut c_char) -
     }
 }
 
+/// Authenticates users against an LDAP directory instead of the Postgres
+/// user store. `bind_dn_template` substitutes `{username}` with the email
+/// to form the bind DN directly; when `search_base` is non-null a
+/// search+bind is performed under it instead. Tokens/sessions are still
+/// kept in Redis exactly as with `init_auth`. The directory is read-only,
+/// so `create_user`, `delete_user` and `admin_delete_user` fail with
+/// `UnsupportedOperation` on an `Auth` created this way.
 #[no_mangle]
-pub extern "C" fn deinit_auth(auth: *mut core::Auth) {
-    unsafe {
-        libc::free(auth as *mut c_void);
+pub extern "C" fn init_auth_ldap(
+    ldap_url: *mut c_char,
+    bind_dn_template: *mut c_char,
+    search_base: *mut c_char,
+    redis_url: *mut c_char,
+) -> AuthResult {
+    fn error(code: i8) -> AuthResult {
+        AuthResult {
+            status_code: StatusCode::Err,
+            result: AuthUnion { err: code.into() },
+        }
+    }
+    let ldap_url = match unsafe { CStr::from_ptr(ldap_url) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return error(-9),
+    };
+    let bind_dn_template = match unsafe { CStr::from_ptr(bind_dn_template) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return error(-9),
+    };
+    let search_base = if search_base.is_null() {
+        None
+    } else {
+        match unsafe { CStr::from_ptr(search_base) }.to_str() {
+            Ok(s) => Some(s.to_string()),
+            Err(_) => return error(-9),
+        }
+    };
+    let redis_url = match unsafe { CStr::from_ptr(redis_url) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return error(-9),
+    };
+    let runtime = build_runtime();
+    let auth = runtime.block_on(async {
+        core::init_auth_ldap(
+            ldap_url.to_string(),
+            bind_dn_template.to_string(),
+            search_base,
+            redis_url.to_string(),
+        )
+        .await
+    });
+    let mut auth = match auth {
+        Ok(a) => a,
+        Err(err) => return error(get_error_code(err)),
+    };
+    auth.set_runtime(runtime);
+    AuthResult {
+        status_code: StatusCode::Ok,
+        result: AuthUnion {
+            ok: Box::into_raw(Box::new(auth)),
+        },
     }
 }
 
+#[no_mangle]
+pub extern "C" fn deinit_auth(auth: *mut core::Auth) {
+    // `Auth` was allocated with `Box::into_raw` and now owns a full
+    // `Runtime` (see `init_auth`), so it must be dropped properly —
+    // `libc::free` would skip the destructor and leak its worker threads.
+    drop(unsafe { Box::from_raw(auth) });
+}
+
 #[no_mangle]
 pub extern "C" fn create_user(
     auth: *mut core::Auth,
@@ -115,18 +339,14 @@ pub extern "C" fn create_user(
         Err(_) => return -9,
     };
 
-    match tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()
-        .unwrap()
-        .block_on(async {
-            core::create_user(
-                unsafe { &mut *auth },
-                email.to_string(),
-                password.to_string(),
-            )
-            .await
-        }) {
+    match unsafe { (*auth).runtime() }.block_on(async {
+        core::create_user(
+            unsafe { &mut *auth },
+            email.to_string(),
+            password.to_string(),
+        )
+        .await
+    }) {
         Ok(_) => 0,
         Err(err) => get_error_code(err).into(),
     }
@@ -152,35 +372,105 @@ pub extern "C" fn login(
         Ok(s) => s,
         Err(_) => return error(-9),
     };
-    match tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()
-        .unwrap()
-        .block_on(async {
-            core::login(
-                unsafe { &mut *auth },
-                email.to_string(),
-                password.to_string(),
-            )
-            .await
-        }) {
-        Ok(token) => CharResult {
-            status_code: StatusCode::Ok,
-            result: CharUnion {
-                ok: {
-                    let mut chars: Vec<c_char> =
-                        token.chars().map(|x| x as c_char).collect::<Vec<c_char>>();
-                    chars.push('\0' as c_char);
-                    unsafe {
-                        let ptr = libc::malloc(mem::size_of::<i8>() * chars.len()) as *mut c_char;
-                        for i in 0..chars.len() {
-                            *ptr.offset(i.try_into().unwrap()) = chars[i];
-                        }
-                        ptr
-                    }
-                },
-            },
-        },
+    match unsafe { (*auth).runtime() }.block_on(async {
+        core::login(
+            unsafe { &mut *auth },
+            email.to_string(),
+            password.to_string(),
+        )
+        .await
+    }) {
+        Ok(token) => char_result_ok(&token),
+        Err(err) => error(get_error_code(err)),
+    }
+}
+
+/// Generates a new TOTP secret for the user identified by `token`, stores
+/// it (inactive until `confirm_totp` is called), and returns an
+/// `otpauth://totp/...` provisioning URI the caller can render as a QR
+/// code.
+#[no_mangle]
+pub extern "C" fn enroll_totp(auth: *mut core::Auth, token: *mut c_char) -> CharResult {
+    fn error(code: i8) -> CharResult {
+        CharResult {
+            status_code: StatusCode::Err,
+            result: CharUnion { err: code.into() },
+        }
+    }
+    let token = match unsafe { CStr::from_ptr(token) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return error(-9),
+    };
+    match unsafe { (*auth).runtime() }
+        .block_on(async { core::enroll_totp(unsafe { &mut *auth }, token.to_string()).await })
+    {
+        Ok(uri) => char_result_ok(&uri),
+        Err(err) => error(get_error_code(err)),
+    }
+}
+
+/// Activates 2FA for the user identified by `token` once they prove
+/// possession of the enrolled secret with a valid `code`.
+#[no_mangle]
+pub extern "C" fn confirm_totp(
+    auth: *mut core::Auth,
+    token: *mut c_char,
+    code: *mut c_char,
+) -> c_int {
+    let token = match unsafe { CStr::from_ptr(token) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return -9,
+    };
+    let code = match unsafe { CStr::from_ptr(code) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return -9,
+    };
+    match unsafe { (*auth).runtime() }.block_on(async {
+        core::confirm_totp(unsafe { &mut *auth }, token.to_string(), code.to_string()).await
+    }) {
+        Ok(_) => 0,
+        Err(err) => get_error_code(err).into(),
+    }
+}
+
+/// Like `login`, but for users with 2FA enabled: fails with a distinct
+/// error code (`IncorrectTotpCode`/`TotpRequired`) when `code` is missing
+/// or wrong, so the caller can prompt for it.
+#[no_mangle]
+pub extern "C" fn login_totp(
+    auth: *mut core::Auth,
+    email: *mut c_char,
+    password: *mut c_char,
+    code: *mut c_char,
+) -> CharResult {
+    fn error(code: i8) -> CharResult {
+        CharResult {
+            status_code: StatusCode::Err,
+            result: CharUnion { err: code.into() },
+        }
+    }
+    let email = match unsafe { CStr::from_ptr(email) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return error(-9),
+    };
+    let password = match unsafe { CStr::from_ptr(password) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return error(-9),
+    };
+    let code = match unsafe { CStr::from_ptr(code) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return error(-9),
+    };
+    match unsafe { (*auth).runtime() }.block_on(async {
+        core::login_totp(
+            unsafe { &mut *auth },
+            email.to_string(),
+            password.to_string(),
+            code.to_string(),
+        )
+        .await
+    }) {
+        Ok(token) => char_result_ok(&token),
         Err(err) => error(get_error_code(err)),
     }
 }
@@ -231,20 +521,16 @@ pub extern "C" fn update_user(
     } else {
         None
     };
-    match tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()
-        .unwrap()
-        .block_on(async {
-            core::update_user(
-                unsafe { &mut *auth },
-                token.to_string(),
-                email,
-                password,
-                logout,
-            )
-            .await
-        }) {
+    match unsafe { (*auth).runtime() }.block_on(async {
+        core::update_user(
+            unsafe { &mut *auth },
+            token.to_string(),
+            email,
+            password,
+            logout,
+        )
+        .await
+    }) {
         Ok(_) => 0,
         Err(err) => get_error_code(err).into(),
     }
@@ -253,12 +539,17 @@ pub extern "C" fn update_user(
 #[no_mangle]
 pub extern "C" fn admin_update_user(
     auth: *mut core::Auth,
-    token: *mut c_char,
+    admin_token: *mut c_char,
+    target_email: *mut c_char,
     email: *mut c_char,
     password: *mut c_char,
     logout: bool,
 ) -> c_int {
-    let token = match unsafe { CStr::from_ptr(token) }.to_str() {
+    let admin_token = match unsafe { CStr::from_ptr(admin_token) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return -9,
+    };
+    let target_email = match unsafe { CStr::from_ptr(target_email) }.to_str() {
         Ok(s) => s,
         Err(_) => return -9,
     };
@@ -284,20 +575,17 @@ pub extern "C" fn admin_update_user(
     } else {
         None
     };
-    match tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()
-        .unwrap()
-        .block_on(async {
-            core::admin_update_user(
-                unsafe { &mut *auth },
-                token.to_string(),
-                email,
-                password,
-                logout,
-            )
-            .await
-        }) {
+    match unsafe { (*auth).runtime() }.block_on(async {
+        core::admin_update_user(
+            unsafe { &mut *auth },
+            admin_token.to_string(),
+            target_email.to_string(),
+            email,
+            password,
+            logout,
+        )
+        .await
+    }) {
         Ok(_) => 0,
         Err(err) => get_error_code(err).into(),
     }
@@ -309,10 +597,7 @@ pub extern "C" fn delete_user(auth: *mut core::Auth, token: *mut c_char) -> c_in
         Ok(s) => s,
         Err(_) => return -9,
     };
-    match tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()
-        .unwrap()
+    match unsafe { (*auth).runtime() }
         .block_on(async { core::delete_user(unsafe { &mut *auth }, token.to_string()).await })
     {
         Ok(_) => 0,
@@ -321,18 +606,23 @@ pub extern "C" fn delete_user(auth: *mut core::Auth, token: *mut c_char) -> c_in
 }
 
 #[no_mangle]
-pub extern "C" fn admin_delete_user(auth: *mut core::Auth, filter: *mut c_char) -> c_int {
+pub extern "C" fn admin_delete_user(
+    auth: *mut core::Auth,
+    admin_token: *mut c_char,
+    filter: *mut c_char,
+) -> c_int {
+    let admin_token = match unsafe { CStr::from_ptr(admin_token) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return -9,
+    };
     let filter = match unsafe { CStr::from_ptr(filter) }.to_str() {
         Ok(s) => s,
         Err(_) => return -9,
     };
-    match tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()
-        .unwrap()
-        .block_on(async {
-            core::admin_delete_user(unsafe { &mut *auth }, filter.to_string()).await
-        }) {
+    match unsafe { (*auth).runtime() }.block_on(async {
+        core::admin_delete_user(unsafe { &mut *auth }, admin_token.to_string(), filter.to_string())
+            .await
+    }) {
         Ok(_) => 0,
         Err(err) => get_error_code(err).into(),
     }
@@ -350,32 +640,12 @@ pub extern "C" fn verify_token(auth: *mut core::Auth, token: *mut c_char) -> Cha
         Ok(s) => s,
         Err(_) => return error(-9),
     };
-    match tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()
-        .unwrap()
+    match unsafe { (*auth).runtime() }
         .block_on(async { core::verify_token(unsafe { &mut *auth }, token.to_string()).await })
     {
         Ok(result) => {
             if result != "" {
-                CharResult {
-                    status_code: StatusCode::Ok,
-                    result: CharUnion {
-                        ok: {
-                            let mut chars: Vec<c_char> =
-                                result.chars().map(|x| x as c_char).collect::<Vec<c_char>>();
-                            chars.push('\0' as c_char);
-                            unsafe {
-                                let ptr =
-                                    libc::malloc(mem::size_of::<i8>() * chars.len()) as *mut c_char;
-                                for i in 0..chars.len() {
-                                    *ptr.offset(i.try_into().unwrap()) = chars[i];
-                                }
-                                ptr
-                            }
-                        },
-                    },
-                }
+                char_result_ok(&result)
             } else {
                 error(1)
             }
@@ -383,3 +653,176 @@ pub extern "C" fn verify_token(auth: *mut core::Auth, token: *mut c_char) -> Cha
         Err(err) => error(get_error_code(err)),
     }
 }
+
+/// Like `verify_token`, but also returns the caller's role membership as a
+/// serialized `{ "user": ..., "roles": [...] }` object, so the C caller can
+/// make authorization decisions instead of only knowing who the user is.
+#[no_mangle]
+pub extern "C" fn verify_token_roles(auth: *mut core::Auth, token: *mut c_char) -> CharResult {
+    fn error(code: i8) -> CharResult {
+        CharResult {
+            status_code: StatusCode::Err,
+            result: CharUnion { err: code.into() },
+        }
+    }
+    let token = match unsafe { CStr::from_ptr(token) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return error(-9),
+    };
+    match unsafe { (*auth).runtime() }.block_on(async {
+        core::verify_token_roles(unsafe { &mut *auth }, token.to_string()).await
+    }) {
+        Ok(result) => char_result_ok(&result),
+        Err(err) => error(get_error_code(err)),
+    }
+}
+
+/// Adds `email` to `role`. Requires `admin_token` to carry the admin role.
+#[no_mangle]
+pub extern "C" fn admin_add_user_to_role(
+    auth: *mut core::Auth,
+    admin_token: *mut c_char,
+    email: *mut c_char,
+    role: *mut c_char,
+) -> c_int {
+    let admin_token = match unsafe { CStr::from_ptr(admin_token) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return -9,
+    };
+    let email = match unsafe { CStr::from_ptr(email) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return -9,
+    };
+    let role = match unsafe { CStr::from_ptr(role) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return -9,
+    };
+    match unsafe { (*auth).runtime() }.block_on(async {
+        core::admin_add_user_to_role(
+            unsafe { &mut *auth },
+            admin_token.to_string(),
+            email.to_string(),
+            role.to_string(),
+        )
+        .await
+    }) {
+        Ok(_) => 0,
+        Err(err) => get_error_code(err).into(),
+    }
+}
+
+/// Removes `email` from `role`. Requires `admin_token` to carry the admin
+/// role.
+#[no_mangle]
+pub extern "C" fn admin_remove_user_from_role(
+    auth: *mut core::Auth,
+    admin_token: *mut c_char,
+    email: *mut c_char,
+    role: *mut c_char,
+) -> c_int {
+    let admin_token = match unsafe { CStr::from_ptr(admin_token) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return -9,
+    };
+    let email = match unsafe { CStr::from_ptr(email) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return -9,
+    };
+    let role = match unsafe { CStr::from_ptr(role) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return -9,
+    };
+    match unsafe { (*auth).runtime() }.block_on(async {
+        core::admin_remove_user_from_role(
+            unsafe { &mut *auth },
+            admin_token.to_string(),
+            email.to_string(),
+            role.to_string(),
+        )
+        .await
+    }) {
+        Ok(_) => 0,
+        Err(err) => get_error_code(err).into(),
+    }
+}
+
+/// Returns the caller's active sessions as a serialized array, each with
+/// the session id (or a masked prefix), creation time, and the idle/last-
+/// seen timestamp recorded on each `verify_token` call.
+#[no_mangle]
+pub extern "C" fn list_sessions(auth: *mut core::Auth, token: *mut c_char) -> CharResult {
+    fn error(code: i8) -> CharResult {
+        CharResult {
+            status_code: StatusCode::Err,
+            result: CharUnion { err: code.into() },
+        }
+    }
+    let token = match unsafe { CStr::from_ptr(token) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return error(-9),
+    };
+    match unsafe { (*auth).runtime() }
+        .block_on(async { core::list_sessions(unsafe { &mut *auth }, token.to_string()).await })
+    {
+        Ok(sessions) => char_result_ok(&sessions),
+        Err(err) => error(get_error_code(err)),
+    }
+}
+
+/// Revokes one of the caller's own sessions by id, e.g. to sign out a
+/// single device without logging out everywhere.
+#[no_mangle]
+pub extern "C" fn revoke_session(
+    auth: *mut core::Auth,
+    token: *mut c_char,
+    target_session_id: *mut c_char,
+) -> c_int {
+    let token = match unsafe { CStr::from_ptr(token) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return -9,
+    };
+    let target_session_id = match unsafe { CStr::from_ptr(target_session_id) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return -9,
+    };
+    match unsafe { (*auth).runtime() }.block_on(async {
+        core::revoke_session(
+            unsafe { &mut *auth },
+            token.to_string(),
+            target_session_id.to_string(),
+        )
+        .await
+    }) {
+        Ok(_) => 0,
+        Err(err) => get_error_code(err).into(),
+    }
+}
+
+/// Logs `user_email` out everywhere by revoking all of their active
+/// sessions. Requires `admin_token` to carry the admin role.
+#[no_mangle]
+pub extern "C" fn admin_revoke_all_sessions(
+    auth: *mut core::Auth,
+    admin_token: *mut c_char,
+    user_email: *mut c_char,
+) -> c_int {
+    let admin_token = match unsafe { CStr::from_ptr(admin_token) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return -9,
+    };
+    let user_email = match unsafe { CStr::from_ptr(user_email) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return -9,
+    };
+    match unsafe { (*auth).runtime() }.block_on(async {
+        core::admin_revoke_all_sessions(
+            unsafe { &mut *auth },
+            admin_token.to_string(),
+            user_email.to_string(),
+        )
+        .await
+    }) {
+        Ok(_) => 0,
+        Err(err) => get_error_code(err).into(),
+    }
+}